@@ -0,0 +1,486 @@
+// Types describing the result of walking & scoring a dependency tree for
+// `cargo crev verify`, and the column selection used to print it.
+
+pub mod print_term;
+
+use cargo::core::PackageId;
+use chrono::{DateTime, Utc};
+use crev_lib::VerificationStatus;
+use semver::Version;
+use std::collections::HashSet;
+
+/// Which optional columns `verify` should print
+///
+/// Each `show_*` method gates one column in `print_term::print_table`, and
+/// is toggled by a CLI flag on the `verify` subcommand.
+#[derive(Debug, Clone, Default)]
+pub struct CrateVerifyColumns {
+    pub digest: bool,
+    pub reviews: bool,
+    pub downloads: bool,
+    pub owners: bool,
+    pub issues: bool,
+    pub loc: bool,
+    pub geiger: bool,
+    pub flags: bool,
+    pub leftpad_index: bool,
+    pub latest_trusted: bool,
+    pub score: bool,
+    pub sizes: bool,
+    pub freshness: bool,
+}
+
+impl CrateVerifyColumns {
+    pub fn show_digest(&self) -> bool {
+        self.digest
+    }
+    pub fn show_reviews(&self) -> bool {
+        self.reviews
+    }
+    pub fn show_downloads(&self) -> bool {
+        self.downloads
+    }
+    pub fn show_owners(&self) -> bool {
+        self.owners
+    }
+    pub fn show_issues(&self) -> bool {
+        self.issues
+    }
+    pub fn show_loc(&self) -> bool {
+        self.loc
+    }
+    pub fn show_geiger(&self) -> bool {
+        self.geiger
+    }
+    pub fn show_flags(&self) -> bool {
+        self.flags
+    }
+    pub fn show_leftpad_index(&self) -> bool {
+        self.leftpad_index
+    }
+    pub fn show_latest_trusted(&self) -> bool {
+        self.latest_trusted
+    }
+    pub fn show_score(&self) -> bool {
+        self.score
+    }
+    pub fn show_sizes(&self) -> bool {
+        self.sizes
+    }
+    pub fn show_freshness(&self) -> bool {
+        self.freshness
+    }
+}
+
+/// A crate's on-disk footprint, in bytes: the compressed registry tarball,
+/// its unpacked source, and the sum over its resolved dependency subtree
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CrateSizes {
+    pub tarball: Option<u64>,
+    pub uncompressed: Option<u64>,
+    pub transitive: Option<u64>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Count {
+    pub count: u64,
+    pub total: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Downloads {
+    pub version: u64,
+    pub total: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct OwnerSet {
+    owners: HashSet<String>,
+    groups: HashSet<String>,
+}
+
+impl OwnerSet {
+    pub fn to_total_owners(&self) -> usize {
+        self.owners.len()
+    }
+    pub fn to_total_distinct_groups(&self) -> usize {
+        self.groups.len()
+    }
+}
+
+/// Signals that accumulate over a crate's entire dependency subtree
+/// (itself included) when walking in recursive mode
+#[derive(Debug, Clone)]
+pub struct AccumulativeCrateDetails {
+    pub is_local_source_code: bool,
+    pub trust: VerificationStatus,
+    pub owner_set: OwnerSet,
+    pub trusted_issues: Count,
+    pub loc: Option<u64>,
+    pub geiger_count: Option<u64>,
+}
+
+/// The crate's own declared maintenance intent, from `Cargo.toml`'s
+/// `[badges] maintenance.status`
+///
+/// This is distinct from [`AccumulativeCrateDetails::trust`] (crev's
+/// reviewer-driven trust) and from `is_unmaintained` (crev's own heuristic):
+/// it's upstream's own statement about whether the crate is still alive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MaintenanceStatus {
+    ActivelyDeveloped,
+    PassivelyMaintained,
+    AsIs,
+    Experimental,
+    LookingForMaintainer,
+    Deprecated,
+}
+
+impl MaintenanceStatus {
+    /// Parse the raw `maintenance.status` badge value; `"none"` and any
+    /// unrecognized value are treated as unspecified (`None`), same as the
+    /// badge being absent entirely
+    pub fn parse(status: &str) -> Option<Self> {
+        use MaintenanceStatus::*;
+
+        match status {
+            "actively-developed" => Some(ActivelyDeveloped),
+            "passively-maintained" => Some(PassivelyMaintained),
+            "as-is" => Some(AsIs),
+            "experimental" => Some(Experimental),
+            "looking-for-maintainer" => Some(LookingForMaintainer),
+            "deprecated" => Some(Deprecated),
+            _ => None,
+        }
+    }
+
+    /// Two-char code shown in the `flags` column
+    pub fn code(self) -> &'static str {
+        use MaintenanceStatus::*;
+
+        match self {
+            ActivelyDeveloped => "AD",
+            PassivelyMaintained => "PM",
+            AsIs => "AI",
+            Experimental => "EX",
+            LookingForMaintainer => "LM",
+            Deprecated => "DP",
+        }
+    }
+}
+
+/// How stale a crate's latest published release is, independent of whether
+/// the *reviewed* version (`latest_trusted_version`) is newer: this answers
+/// "is upstream even still alive", not "is there a newer reviewed version"
+#[derive(Debug, Clone, Copy)]
+pub struct Freshness {
+    pub last_release: DateTime<Utc>,
+    pub releases_behind: u32,
+}
+
+/// Time constant for the exponential freshness falloff: a release this many
+/// days old scores `1/e`. Chosen so releases within the last few months
+/// stay close to 1.0 and the score trends toward 0 by around two years old.
+const FRESHNESS_DECAY_DAYS: f64 = 270.0;
+
+/// A `[0, 1]` freshness score for a release, 1.0 meaning "published just
+/// now", decaying exponentially as `last_release` recedes into the past
+pub fn freshness_score(last_release: DateTime<Utc>, now: DateTime<Utc>) -> f64 {
+    let age_days = (now - last_release).num_days().max(0) as f64;
+    (-age_days / FRESHNESS_DECAY_DAYS).exp()
+}
+
+/// Everything `print_term::print_table` needs to know about one resolved crate
+#[derive(Debug, Clone)]
+pub struct CrateDetails {
+    pub digest: Option<String>,
+    pub accumulative: AccumulativeCrateDetails,
+    pub version_reviews: Count,
+    pub downloads: Option<Downloads>,
+    pub known_owners: Option<Count>,
+    pub latest_trusted_version: Option<Version>,
+    pub leftpad_idx: usize,
+    pub has_custom_build: bool,
+    pub is_unmaintained: bool,
+    pub maintenance_status: Option<MaintenanceStatus>,
+    pub sizes: CrateSizes,
+    pub freshness: Option<Freshness>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CrateInfo {
+    pub id: PackageId,
+}
+
+#[derive(Debug, Clone)]
+pub struct CrateStats {
+    pub info: CrateInfo,
+    pub details: CrateDetails,
+}
+
+impl CrateStats {
+    pub fn details(&self) -> CrateDetails {
+        self.details.clone()
+    }
+
+    pub fn has_custom_build(&self) -> bool {
+        self.details.has_custom_build
+    }
+
+    pub fn is_unmaintained(&self) -> bool {
+        self.details.is_unmaintained
+    }
+
+    pub fn maintenance_status(&self) -> Option<MaintenanceStatus> {
+        self.details.maintenance_status
+    }
+
+    pub fn freshness(&self) -> Option<Freshness> {
+        self.details.freshness
+    }
+}
+
+/// Weights used to combine `CrateDetails`'s signals into a single 0-100
+/// "quality score", kept in their own struct so they're testable and
+/// overridable independently of the scoring formula itself.
+#[derive(Debug, Clone, Copy)]
+pub struct ScoreWeights {
+    pub reviews: f64,
+    pub downloads: f64,
+    pub owners: f64,
+    pub issues_penalty: f64,
+    pub geiger_penalty: f64,
+    pub unmaintained_penalty: f64,
+    pub custom_build_penalty: f64,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        ScoreWeights {
+            reviews: 0.35,
+            downloads: 0.2,
+            owners: 0.15,
+            issues_penalty: 0.2,
+            geiger_penalty: 0.1,
+            unmaintained_penalty: 0.3,
+            custom_build_penalty: 0.1,
+        }
+    }
+}
+
+/// A gate applied multiplicatively to the weighted sum, so an untrusted
+/// crate scores lower than a trusted one with the same review/download
+/// signals.
+fn trust_gate(trust: VerificationStatus) -> f64 {
+    use VerificationStatus::*;
+
+    match trust {
+        Local => 1.0,
+        Verified => 1.0,
+        None => 0.6,
+        Negative => 0.1,
+    }
+}
+
+/// Compute a normalized 0-100 health score for a crate from the signals
+/// already carried by `CrateDetails`, so a big dependency tree can be
+/// sorted/triaged at a glance instead of eyeballing six separate columns.
+pub fn compute_quality_score(details: &CrateDetails, weights: &ScoreWeights) -> f64 {
+    let acc = &details.accumulative;
+
+    let reviews = 1.0 - (-(details.version_reviews.count as f64) / 3.0).exp();
+
+    let downloads = details
+        .downloads
+        .map(|d| ((d.total as f64 + 1.0).log10() / 7.0).min(1.0))
+        .unwrap_or(0.0);
+
+    let owners = details
+        .known_owners
+        .map(|o| ((o.count as f64 + 1.0).log10() / 1.5).min(1.0))
+        .unwrap_or(0.0);
+
+    let issues_penalty = (acc.trusted_issues.count.min(5) as f64 / 5.0) * weights.issues_penalty;
+
+    let geiger_penalty = match (acc.geiger_count, acc.loc) {
+        (Some(geiger), Some(loc)) if loc > 0 => {
+            (geiger as f64 / loc as f64).min(1.0) * weights.geiger_penalty
+        }
+        _ => 0.0,
+    };
+
+    let unmaintained_penalty = if details.is_unmaintained {
+        weights.unmaintained_penalty
+    } else {
+        0.0
+    };
+    let custom_build_penalty = if details.has_custom_build {
+        weights.custom_build_penalty
+    } else {
+        0.0
+    };
+
+    // Divide by the total positive weight so a crate that maxes out every
+    // positive signal reaches 1.0 whatever the weights add up to.
+    let positive_total = weights.reviews + weights.downloads + weights.owners;
+    let positive = if positive_total > 0.0 {
+        (weights.reviews * reviews + weights.downloads * downloads + weights.owners * owners)
+            / positive_total
+    } else {
+        0.0
+    };
+
+    let raw = positive
+        - issues_penalty
+        - geiger_penalty
+        - unmaintained_penalty
+        - custom_build_penalty;
+
+    let gated = raw.max(0.0).min(1.0) * trust_gate(acc.trust);
+
+    gated.max(0.0).min(1.0) * 100.0
+}
+
+pub fn latest_trusted_version_string(
+    current: &Version,
+    latest_trusted: &Option<Version>,
+) -> String {
+    match latest_trusted {
+        Some(v) if v > current => v.to_string(),
+        _ => "-".into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn details_with(
+        version_reviews: u64,
+        downloads: Option<u64>,
+        trust: VerificationStatus,
+    ) -> CrateDetails {
+        CrateDetails {
+            digest: None,
+            accumulative: AccumulativeCrateDetails {
+                is_local_source_code: false,
+                trust,
+                owner_set: OwnerSet::default(),
+                trusted_issues: Count::default(),
+                loc: None,
+                geiger_count: None,
+            },
+            version_reviews: Count {
+                count: version_reviews,
+                total: version_reviews,
+            },
+            downloads: downloads.map(|total| Downloads { version: total, total }),
+            known_owners: None,
+            latest_trusted_version: None,
+            leftpad_idx: 0,
+            has_custom_build: false,
+            is_unmaintained: false,
+            maintenance_status: None,
+            sizes: CrateSizes::default(),
+            freshness: None,
+        }
+    }
+
+    #[test]
+    fn quality_score_is_clamped_to_0_100() {
+        let weights = ScoreWeights::default();
+
+        let worst = details_with(0, None, VerificationStatus::Negative);
+        let best = details_with(100, Some(1_000_000_000), VerificationStatus::Verified);
+
+        assert!(compute_quality_score(&worst, &weights) >= 0.0);
+        assert!(compute_quality_score(&best, &weights) <= 100.0);
+    }
+
+    #[test]
+    fn quality_score_rewards_more_reviews_and_downloads() {
+        let weights = ScoreWeights::default();
+
+        let few = details_with(0, Some(10), VerificationStatus::None);
+        let many = details_with(50, Some(1_000_000), VerificationStatus::None);
+
+        assert!(compute_quality_score(&many, &weights) > compute_quality_score(&few, &weights));
+    }
+
+    #[test]
+    fn quality_score_penalizes_unmaintained_and_custom_build() {
+        let weights = ScoreWeights::default();
+
+        let clean = details_with(5, Some(1_000), VerificationStatus::Verified);
+        let mut flagged = clean.clone();
+        flagged.is_unmaintained = true;
+        flagged.has_custom_build = true;
+
+        assert!(compute_quality_score(&clean, &weights) > compute_quality_score(&flagged, &weights));
+
+        // sanity: mutating a clone didn't also mutate the original
+        assert!(!clean.is_unmaintained);
+    }
+
+    #[test]
+    fn trust_gate_orders_negative_below_none_below_verified() {
+        assert!(trust_gate(VerificationStatus::Negative) < trust_gate(VerificationStatus::None));
+        assert!(trust_gate(VerificationStatus::None) < trust_gate(VerificationStatus::Verified));
+        assert_eq!(
+            trust_gate(VerificationStatus::Verified),
+            trust_gate(VerificationStatus::Local)
+        );
+    }
+
+    #[test]
+    fn freshness_score_is_1_for_a_release_right_now() {
+        let now = Utc::now();
+        assert!((freshness_score(now, now) - 1.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn freshness_score_decays_toward_0_as_release_ages() {
+        let now = Utc::now();
+        let recent = now - Duration::days(30);
+        let old = now - Duration::days(800);
+
+        let recent_score = freshness_score(recent, now);
+        let old_score = freshness_score(old, now);
+
+        assert!(recent_score > old_score);
+        assert!(old_score >= 0.0 && old_score < 0.1);
+    }
+
+    #[test]
+    fn maintenance_status_parses_known_values() {
+        assert_eq!(
+            MaintenanceStatus::parse("actively-developed"),
+            Some(MaintenanceStatus::ActivelyDeveloped)
+        );
+        assert_eq!(
+            MaintenanceStatus::parse("deprecated"),
+            Some(MaintenanceStatus::Deprecated)
+        );
+    }
+
+    #[test]
+    fn maintenance_status_treats_none_and_unknown_as_unspecified() {
+        assert_eq!(MaintenanceStatus::parse("none"), None);
+        assert_eq!(MaintenanceStatus::parse("made-up-value"), None);
+    }
+
+    #[test]
+    fn latest_trusted_version_string_only_reports_newer_versions() {
+        let current = Version::parse("1.0.0").unwrap();
+        let older = Some(Version::parse("0.9.0").unwrap());
+        let newer = Some(Version::parse("1.1.0").unwrap());
+
+        assert_eq!(latest_trusted_version_string(&current, &older), "-");
+        assert_eq!(latest_trusted_version_string(&current, &None), "-");
+        assert_eq!(
+            latest_trusted_version_string(&current, &newer),
+            "1.1.0".to_string()
+        );
+    }
+}
@@ -3,235 +3,477 @@
 
 use super::*;
 use crate::term::{self, *};
+use chrono::Utc;
+use std::collections::HashMap;
 
-fn pad_left_manually(s: String, width: usize) -> String {
-    if s.len() <= width {
-        let padding = std::iter::repeat(" ")
-            .take(width - s.len())
-            .collect::<String>();
-        format!("{}{}", s, padding)
+/// Threshold above which the transitive footprint is colored as a warning,
+/// mirroring how the download/owner thresholds work; it is a compile-time
+/// constant and cannot be changed from the command line or config
+const TRANSITIVE_SIZE_WARN_BYTES: u64 = 10 * 1024 * 1024;
+
+fn format_size(bytes: u64) -> String {
+    const KIB: f64 = 1024.0;
+    const MIB: f64 = KIB * 1024.0;
+
+    let bytes = bytes as f64;
+    if bytes >= MIB {
+        format!("{:.1}MiB", bytes / MIB)
     } else {
-        s
+        format!("{:.0}KiB", bytes / KIB)
     }
 }
 
-pub fn print_header(_term: &mut Term, columns: &CrateVerifyColumns) {
-    if columns.show_digest() {
-        eprint!("{:<43} ", "digest");
+/// Render an age in days as a short human duration, e.g. `"12d"`, `"3mo"`,
+/// `"2y"`
+fn format_age(age_days: i64) -> String {
+    const DAYS_PER_MONTH: i64 = 30;
+    const DAYS_PER_YEAR: i64 = 365;
+
+    let age_days = age_days.max(0);
+    if age_days >= DAYS_PER_YEAR {
+        format!("{}y", age_days / DAYS_PER_YEAR)
+    } else if age_days >= DAYS_PER_MONTH {
+        format!("{}mo", age_days / DAYS_PER_MONTH)
+    } else {
+        format!("{}d", age_days)
     }
+}
 
-    eprint!("{:>6} ", "status");
+/// One rendered table cell: its text plus an optional color
+///
+/// Widths are measured in `chars`, not bytes, so multibyte crate names
+/// don't throw off alignment the way the old byte-length padding did.
+#[derive(Debug, Clone)]
+struct Cell {
+    text: String,
+    color: Option<::term::color::Color>,
+    align_right: bool,
+}
 
-    if columns.show_reviews() {
-        eprint!("{:>7} ", "reviews");
+impl Cell {
+    fn new(text: impl Into<String>) -> Self {
+        Cell {
+            text: text.into(),
+            color: None,
+            align_right: true,
+        }
     }
 
-    if columns.show_downloads() {
-        eprint!("{:^18} ", "downloads");
+    fn left(text: impl Into<String>) -> Self {
+        Cell {
+            text: text.into(),
+            color: None,
+            align_right: false,
+        }
     }
 
-    if columns.show_owners() {
-        eprint!("{:>6} ", "owner");
+    fn colored(text: impl Into<String>, color: Option<::term::color::Color>) -> Self {
+        Cell {
+            text: text.into(),
+            color,
+            align_right: true,
+        }
     }
 
-    if columns.show_issues() {
-        eprint!("{:>6} ", "issues");
+    fn width(&self) -> usize {
+        self.text.chars().count()
     }
+}
 
-    if columns.show_loc() {
-        eprint!("{:>6} ", "loc");
-    }
+/// An optional column, identified by the same name as its `CrateVerifyColumns::show_*` flag
+///
+/// `PRIORITY` lists optional columns from lowest to highest priority: when
+/// the table doesn't fit the terminal, columns are dropped starting from
+/// the front of this list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Column {
+    Digest,
+    Downloads,
+    Loc,
+    Geiger,
+    Sizes,
+    Reviews,
+    Owners,
+    Issues,
+    Flags,
+    LeftpadIndex,
+    Score,
+    LatestTrusted,
+    Freshness,
+}
 
-    if columns.show_geiger() {
-        eprint!("{:>6} ", "geiger");
-    }
+const PRIORITY: &[Column] = &[
+    Column::Digest,
+    Column::Downloads,
+    Column::Loc,
+    Column::Geiger,
+    Column::Sizes,
+    Column::Reviews,
+    Column::Owners,
+    Column::Issues,
+    Column::Flags,
+    Column::LeftpadIndex,
+    Column::Score,
+    Column::LatestTrusted,
+    Column::Freshness,
+];
 
-    if columns.show_flags() {
-        eprint!("{:>4} ", "flgs");
-    }
-    if columns.show_leftpad_index() {
-        eprint!("{:>5} ", "lpidx");
+const MIN_WIDTH: usize = 3;
+const MAX_WIDTH: usize = 40;
+const GAP: usize = 1;
+
+fn active_columns(columns: &CrateVerifyColumns) -> Vec<Column> {
+    let mut active = vec![];
+    for &col in PRIORITY {
+        let shown = match col {
+            Column::Digest => columns.show_digest(),
+            Column::Downloads => columns.show_downloads(),
+            Column::Loc => columns.show_loc(),
+            Column::Geiger => columns.show_geiger(),
+            Column::Sizes => columns.show_sizes(),
+            Column::Reviews => columns.show_reviews(),
+            Column::Owners => columns.show_owners(),
+            Column::Issues => columns.show_issues(),
+            Column::Flags => columns.show_flags(),
+            Column::LeftpadIndex => columns.show_leftpad_index(),
+            Column::Score => columns.show_score(),
+            Column::LatestTrusted => columns.show_latest_trusted(),
+            Column::Freshness => columns.show_freshness(),
+        };
+        if shown {
+            active.push(col);
+        }
     }
-    eprintln!("{:<20} {:<15} ", "crate", "version");
+    active
+}
 
-    if columns.show_latest_trusted() {
-        eprintln!("{:<15}", "latest_t");
+fn header_for(col: Column) -> &'static str {
+    match col {
+        Column::Digest => "digest",
+        Column::Downloads => "downloads",
+        Column::Loc => "loc",
+        Column::Geiger => "geiger",
+        Column::Sizes => "tarball/uncmp/trans",
+        Column::Reviews => "reviews",
+        Column::Owners => "owner",
+        Column::Issues => "issues",
+        Column::Flags => "flgs",
+        Column::LeftpadIndex => "lpidx",
+        Column::Score => "score",
+        Column::LatestTrusted => "latest_t",
+        Column::Freshness => "fresh",
     }
 }
 
-#[allow(clippy::collapsible_if)]
-pub fn print_details(
+/// Render the cell for one optional column of one row
+fn cell_for(
+    col: Column,
+    stats: &CrateStats,
     cdep: &CrateDetails,
-    term: &mut Term,
-    columns: &CrateVerifyColumns,
     recursive_mode: bool,
-) -> Result<()> {
-    if columns.show_digest() {
-        print!(
-            "{:43} ",
+    theme: &Theme,
+) -> Cell {
+    match col {
+        Column::Digest => Cell::left(
             cdep.digest
                 .as_ref()
-                .map(|d| d.to_string())
-                .unwrap_or_else(|| "-".into())
-        );
-    }
-    if cdep.accumulative.is_local_source_code {
-        term.print(format_args!("{:6} ", "local"), None)?;
-    } else {
-        term.print(
-            format_args!("{:6} ", cdep.accumulative.trust),
-            term::verification_status_color(cdep.accumulative.trust),
-        )?;
-    }
-
-    if columns.show_reviews() {
-        print!(
-            "{:3} {:3} ",
-            cdep.version_reviews.count, cdep.version_reviews.total
-        );
-    }
-
-    if columns.show_downloads() {
-        if let Some(downloads) = &cdep.downloads {
-            term.print(
-                format_args!("{:>8} ", downloads.version),
+                .map(ToString::to_string)
+                .unwrap_or_else(|| "-".into()),
+        ),
+        Column::Downloads => match &cdep.downloads {
+            Some(downloads) => Cell::colored(
+                format!("{}/{}", downloads.version, downloads.total),
                 if downloads.version < 1000 {
-                    Some(::term::color::YELLOW)
+                    theme.color_for(term::Role::LowDownloads)
                 } else {
                     None
                 },
-            )?;
-            term.print(
-                format_args!("{:>9} ", downloads.total),
-                if downloads.total < 10000 {
-                    Some(::term::color::YELLOW)
+            ),
+            None => Cell::new("?"),
+        },
+        Column::Loc => match cdep.accumulative.loc {
+            Some(loc) => Cell::new(loc.to_string()),
+            None => Cell::new("err"),
+        },
+        Column::Geiger => match cdep.accumulative.geiger_count {
+            Some(geiger_count) => Cell::new(geiger_count.to_string()),
+            None => Cell::new("err"),
+        },
+        Column::Sizes => {
+            let part = |size: Option<u64>, warn: bool| match size {
+                Some(size) if warn && size > TRANSITIVE_SIZE_WARN_BYTES => {
+                    format!("*{}", format_size(size))
+                }
+                Some(size) => format_size(size),
+                None => "err".into(),
+            };
+            let transitive_warn = matches!(cdep.sizes.transitive, Some(s) if s > TRANSITIVE_SIZE_WARN_BYTES);
+            Cell::colored(
+                format!(
+                    "{}/{}/{}",
+                    part(cdep.sizes.tarball, false),
+                    part(cdep.sizes.uncompressed, false),
+                    part(cdep.sizes.transitive, true),
+                ),
+                if transitive_warn {
+                    theme.color_for(term::Role::LargeTransitiveSize)
                 } else {
                     None
                 },
-            )?;
-        } else {
-            term.print(format_args!("{:>8} {:>9} ", "?", "?"), None)?;
+            )
         }
-    }
-
-    if columns.show_owners() {
-        if recursive_mode {
-            term.print(
-                format_args!(
-                    "{:>2} {:>3} ",
+        Column::Reviews => Cell::new(format!(
+            "{}/{}",
+            cdep.version_reviews.count, cdep.version_reviews.total
+        )),
+        Column::Owners => {
+            if recursive_mode {
+                Cell::new(format!(
+                    "{}/{}",
                     cdep.accumulative.owner_set.to_total_owners(),
                     cdep.accumulative.owner_set.to_total_distinct_groups()
-                ),
-                None,
-            )?;
-        } else {
-            if let Some(known_owners) = &cdep.known_owners {
-                term.print(
-                    format_args!("{:>2} ", known_owners.count),
-                    term::known_owners_count_color(known_owners.count),
-                )?;
-                term.print(format_args!("{:>3} ", known_owners.total), None)?;
+                ))
             } else {
-                term.print(
-                    format_args!("{:>2} ", "?"),
-                    term::known_owners_count_color(0),
-                )?;
-                term.print(format_args!("{:>3} ", "?"), None)?;
+                match &cdep.known_owners {
+                    Some(known_owners) => Cell::colored(
+                        format!("{}/{}", known_owners.count, known_owners.total),
+                        term::known_owners_count_color(theme, known_owners.count),
+                    ),
+                    None => Cell::colored("?/?", term::known_owners_count_color(theme, 0)),
+                }
             }
         }
-    }
-
-    if columns.show_issues() {
-        term.print(
-            format_args!("{:2} ", cdep.accumulative.trusted_issues.count),
+        Column::Issues => Cell::colored(
+            format!(
+                "{}/{}",
+                cdep.accumulative.trusted_issues.count, cdep.accumulative.trusted_issues.total
+            ),
             if cdep.accumulative.trusted_issues.count > 0 {
-                Some(::term::color::RED)
-            } else {
-                None
-            },
-        )?;
-        term.print(
-            format_args!("{:3} ", cdep.accumulative.trusted_issues.total),
-            if cdep.accumulative.trusted_issues.total > 0 {
-                Some(::term::color::YELLOW)
+                theme.color_for(term::Role::IssuesPresent)
+            } else if cdep.accumulative.trusted_issues.total > 0 {
+                theme.color_for(term::Role::IssuesKnown)
             } else {
                 None
             },
-        )?;
+        ),
+        Column::Flags => {
+            let maintenance = stats.maintenance_status();
+            let mut text = String::new();
+            text.push_str(if stats.has_custom_build() { "CB" } else { "__" });
+            text.push_str(if stats.is_unmaintained() { "UM" } else { "__" });
+            text.push_str(
+                maintenance
+                    .map(super::MaintenanceStatus::code)
+                    .unwrap_or("__"),
+            );
+            let color = match maintenance {
+                Some(status) => term::maintenance_status_color(theme, status),
+                None if stats.is_unmaintained() => theme.color_for(term::Role::Unmaintained),
+                None => None,
+            };
+            Cell::colored(text, color)
+        }
+        Column::LeftpadIndex => Cell::new(stats.details.leftpad_idx.to_string()),
+        Column::Score => {
+            let score = super::compute_quality_score(cdep, &super::ScoreWeights::default());
+            Cell::colored(format!("{:.0}", score), term::score_color(theme, score))
+        }
+        Column::LatestTrusted => Cell::left(latest_trusted_version_string(
+            &stats.info.id.version(),
+            &cdep.latest_trusted_version,
+        )),
+        Column::Freshness => match &cdep.freshness {
+            Some(freshness) => {
+                let now = Utc::now();
+                let age_days = (now - freshness.last_release).num_days();
+                let mut text = format_age(age_days);
+                if freshness.releases_behind > 0 {
+                    text.push_str(&format!(" (+{})", freshness.releases_behind));
+                }
+                let score = super::freshness_score(freshness.last_release, now);
+                Cell::colored(text, term::release_freshness_color(theme, score))
+            }
+            None => Cell::new("?"),
+        },
     }
+}
 
-    if columns.show_loc() {
-        match cdep.accumulative.loc {
-            Some(loc) => print!("{:>6} ", loc),
-            None => print!("{:>6} ", "err"),
-        }
+fn status_cell(cdep: &CrateDetails, theme: &Theme) -> Cell {
+    if cdep.accumulative.is_local_source_code {
+        Cell::new("local")
+    } else {
+        Cell::colored(
+            cdep.accumulative.trust.to_string(),
+            term::verification_status_color(theme, cdep.accumulative.trust),
+        )
     }
+}
 
-    Ok(())
+fn crate_name_cell(stats: &CrateStats) -> Cell {
+    Cell::left(stats.info.id.name().to_string())
 }
 
-fn print_stats_crate_id(stats: &CrateStats, _term: &mut Term) {
-    print!(
-        "{:<20} {:<15}",
-        stats.info.id.name(),
-        pad_left_manually(
-            stats.info.id.version().to_string()
-                + if stats.info.id.source_id().is_registry() {
-                    ""
-                } else {
-                    "*"
-                },
-            15
-        )
-    );
+fn version_cell(stats: &CrateStats) -> Cell {
+    Cell::left(
+        stats.info.id.version().to_string()
+            + if stats.info.id.source_id().is_registry() {
+                ""
+            } else {
+                "*"
+            },
+    )
 }
 
-pub fn print_dep(
-    stats: &CrateStats,
+/// Best-effort terminal width, falling back to a sane default when it
+/// can't be determined (e.g. output is piped to a file)
+///
+/// `$COLUMNS` wins when a caller has set it explicitly, since that's a
+/// deliberate override; otherwise we query the actual tty size, and only
+/// fall back to a hard-coded default if neither is available.
+fn detect_term_width() -> usize {
+    std::env::var("COLUMNS")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .or_else(|| term_size::dimensions().map(|(w, _)| w))
+        .unwrap_or(120)
+}
+
+/// Print the whole `verify` table: header plus one row per crate
+///
+/// Every row is rendered to a grid of `Cell`s before anything is printed,
+/// so column widths can be measured from actual content rather than
+/// guessed hard-coded widths, and so columns can be dropped in priority
+/// order if the table doesn't fit in the detected terminal width.
+pub fn print_table(
+    stats: &[CrateStats],
     term: &mut Term,
     columns: &CrateVerifyColumns,
     recursive_mode: bool,
+    theme: &Theme,
 ) -> Result<()> {
-    let details = stats.details();
+    let mut active = active_columns(columns);
 
-    print_details(&details, term, columns, recursive_mode)?;
-    if columns.show_geiger() {
-        match details.accumulative.geiger_count {
-            Some(geiger_count) => print!("{:>6} ", geiger_count),
-            None => print!("{:>6} ", "err"),
-        }
-    }
+    let rows: Vec<_> = stats
+        .iter()
+        .map(|s| {
+            let details = s.details();
+            (s, details)
+        })
+        .collect();
 
-    if columns.show_flags() {
-        if stats.has_custom_build() {
-            print!("CB");
-        } else {
-            print!("__");
+    loop {
+        let mut col_widths: HashMap<Column, usize> = HashMap::new();
+        for &col in &active {
+            let header_width = header_for(col).chars().count();
+            let max_cell_width = rows
+                .iter()
+                .map(|(stats, details)| cell_for(col, stats, details, recursive_mode, theme).width())
+                .max()
+                .unwrap_or(0);
+            let width = header_width.max(max_cell_width).max(MIN_WIDTH).min(MAX_WIDTH);
+            col_widths.insert(col, width);
         }
 
-        if stats.is_unmaintained() {
-            term.print(format_args!("UM"), ::term::color::YELLOW)?;
-        } else {
-            print!("__");
+        let status_width = rows
+            .iter()
+            .map(|(_, details)| status_cell(details, theme).width())
+            .max()
+            .unwrap_or(0)
+            .max("status".len());
+        let crate_width = rows
+            .iter()
+            .map(|(stats, _)| crate_name_cell(stats).width())
+            .max()
+            .unwrap_or(0)
+            .max("crate".len());
+        let version_width = rows
+            .iter()
+            .map(|(stats, _)| version_cell(stats).width())
+            .max()
+            .unwrap_or(0)
+            .max("version".len());
+
+        let total: usize = col_widths.values().sum::<usize>()
+            + status_width
+            + crate_width
+            + version_width
+            + GAP * (active.len() + 3);
+
+        if total <= detect_term_width() || active.is_empty() {
+            for &col in &active {
+                eprint!("{:>width$} ", header_for(col), width = col_widths[&col]);
+            }
+            eprintln!(
+                "{:>swidth$} {:<cwidth$} {:<vwidth$}",
+                "status",
+                "crate",
+                "version",
+                swidth = status_width,
+                cwidth = crate_width,
+                vwidth = version_width
+            );
+
+            for (stats, details) in &rows {
+                for &col in &active {
+                    print_cell(
+                        term,
+                        &cell_for(col, stats, details, recursive_mode, theme),
+                        col_widths[&col],
+                    )?;
+                }
+                print_cell(term, &status_cell(details, theme), status_width)?;
+                print_cell(term, &crate_name_cell(stats), crate_width)?;
+                print_cell(term, &version_cell(stats), version_width)?;
+                println!();
+            }
+
+            return Ok(());
         }
-        print!(" ");
+
+        // Drop the lowest-priority remaining optional column and retry.
+        active.remove(0);
     }
+}
 
-    if columns.show_leftpad_index() {
-        print!("{:>5} ", stats.details.leftpad_idx);
+fn print_cell(term: &mut Term, cell: &Cell, width: usize) -> Result<()> {
+    let pad = " ".repeat(width.saturating_sub(cell.width()));
+    if cell.align_right {
+        term.print(format_args!("{}", pad), None)?;
+        term.print(format_args!("{}", cell.text), cell.color)?;
+    } else {
+        term.print(format_args!("{}", cell.text), cell.color)?;
+        term.print(format_args!("{}", pad), None)?;
     }
+    print!(" ");
+    Ok(())
+}
 
-    print_stats_crate_id(stats, term);
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    if columns.show_latest_trusted() {
-        print!(
-            " {}",
-            latest_trusted_version_string(
-                &stats.info.id.version(),
-                &details.latest_trusted_version
-            )
-        );
+    #[test]
+    fn format_size_picks_kib_below_a_mebibyte() {
+        assert_eq!(format_size(0), "0KiB");
+        assert_eq!(format_size(512 * 1024), "512KiB");
+    }
+
+    #[test]
+    fn format_size_switches_to_mib_at_a_mebibyte() {
+        assert_eq!(format_size(1024 * 1024), "1.0MiB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0MiB");
+    }
+
+    #[test]
+    fn format_age_picks_the_coarsest_unit_that_fits() {
+        assert_eq!(format_age(0), "0d");
+        assert_eq!(format_age(12), "12d");
+        assert_eq!(format_age(90), "3mo");
+        assert_eq!(format_age(400), "1y");
+    }
+
+    #[test]
+    fn format_age_clamps_negative_ages_to_zero() {
+        assert_eq!(format_age(-5), "0d");
     }
-    println!();
-    Ok(())
 }
@@ -0,0 +1,64 @@
+use std::path::PathBuf;
+use structopt::StructOpt;
+
+/// Cargo-level flags shared by every `cargo crev` subcommand
+///
+/// These mirror the flags `cargo` itself accepts for feature selection and
+/// resolution, and get threaded straight through to `cargo`'s own
+/// `Config`/`Workspace` machinery by `Repo::auto_open_cwd`.
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CargoOpts {
+    #[structopt(long = "manifest-path", parse(from_os_str))]
+    pub manifest_path: Option<PathBuf>,
+
+    #[structopt(long = "features")]
+    pub features: Option<String>,
+
+    #[structopt(long = "all-features")]
+    pub all_features: bool,
+
+    #[structopt(long = "no-default-features")]
+    pub no_default_features: bool,
+
+    #[structopt(long = "no-dev-dependencies")]
+    pub no_dev_dependencies: bool,
+
+    /// `--target[=TRIPLE]`; `Some(None)` means "use the host target"
+    #[structopt(long = "target")]
+    pub target: Option<Option<String>>,
+
+    #[structopt(short = "Z", long = "unstable-flags")]
+    pub unstable_flags: Vec<String>,
+
+    /// Run without accessing the network, failing if the cache is missing data
+    #[structopt(long = "offline")]
+    pub offline: bool,
+
+    /// Require `Cargo.lock` to already be up to date; never create or update it
+    #[structopt(long = "frozen")]
+    pub frozen: bool,
+}
+
+/// Selects a single crate (optionally a specific version) out of the
+/// current workspace's dependency graph
+#[derive(Debug, StructOpt, Clone, Default)]
+pub struct CrateSelector {
+    pub name: Option<String>,
+
+    #[structopt(long = "version")]
+    pub version: Option<String>,
+
+    /// Look the crate up directly in the registry instead of requiring it
+    /// to be a dependency of the current workspace
+    #[structopt(short = "u", long = "unrelated")]
+    pub unrelated: bool,
+}
+
+impl CrateSelector {
+    pub fn ensure_name_given(&self) -> crate::prelude::Result<()> {
+        if self.name.is_none() {
+            failure::bail!("Crate name required");
+        }
+        Ok(())
+    }
+}
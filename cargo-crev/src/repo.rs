@@ -16,6 +16,7 @@ use crev_common::convert::OptionDeref;
 use crev_lib;
 use failure::format_err;
 use petgraph::graph::NodeIndex;
+use semver::VersionReq;
 use std::{
     collections::{hash_map::Entry, BTreeSet, HashMap, HashSet},
     env,
@@ -33,9 +34,22 @@ struct Node {
     metadata: ManifestMetadata,
 }
 
+/// A dependency edge, plus the targets under which it is actually active
+///
+/// `targets` is `None` when the dependency has no `cfg`/target platform
+/// restriction at all (the common case), or when `build_graph` was given no
+/// targets to check the restriction against; when `Some`, it's the subset of
+/// the targets `build_graph` was asked about that this edge matched, which
+/// lets us tell apart "pulled in everywhere" from "only on wasm32".
+#[derive(Debug, Clone)]
+struct DepEdge {
+    kind: Kind,
+    targets: Option<BTreeSet<String>>,
+}
+
 #[derive(Debug)]
 pub struct Graph {
-    graph: petgraph::Graph<Node, Kind>,
+    graph: petgraph::Graph<Node, DepEdge>,
     nodes: HashMap<PackageId, NodeIndex>,
 }
 
@@ -72,11 +86,28 @@ impl Graph {
             .map(move |node_idx| self.graph.node_weight(node_idx).unwrap().id)
     }
 
-    pub fn get_recursive_dependencies_of(&self, root_pkg_id: PackageId) -> HashSet<PackageId> {
+    /// Every crate reachable from `root_pkg_id`, plus the targets under
+    /// which it's actually pulled in.
+    ///
+    /// The value is `EdgeTargets::All` when no edge on the first path found
+    /// to that crate is target-restricted, or `EdgeTargets::Only(triples)`
+    /// when at least one edge along that path is, in which case it's the
+    /// intersection of the restricted edges' targets — e.g. a crate only
+    /// ever reached via a `wasm32-unknown-unknown`-only dependency is
+    /// `Only({ "wasm32-unknown-unknown" })`. Like the rest of this graph, this is
+    /// intentionally coarse: a crate reachable via more than one path is
+    /// reported using whichever path the traversal happens to visit first,
+    /// not the union of every path.
+    pub fn get_recursive_dependencies_of(
+        &self,
+        root_pkg_id: PackageId,
+    ) -> HashMap<PackageId, EdgeTargets> {
         let mut pending = BTreeSet::new();
+        let mut reached: HashMap<PackageId, EdgeTargets> = HashMap::new();
         let mut processed = HashSet::new();
 
         pending.insert(root_pkg_id);
+        reached.insert(root_pkg_id, EdgeTargets::All);
 
         while let Some(pkg_id) = pending.iter().next().cloned() {
             pending.remove(&pkg_id);
@@ -87,12 +118,18 @@ impl Graph {
                 processed.insert(pkg_id);
             }
 
+            let targets_to_here = reached[&pkg_id].clone();
+
             if let Some(node_idx) = self.nodes.get(&pkg_id) {
-                for node_idx in self
+                for edge_idx in self
                     .graph
-                    .neighbors_directed(*node_idx, petgraph::Direction::Outgoing)
+                    .edges_directed(*node_idx, petgraph::Direction::Outgoing)
                 {
-                    pending.insert(self.graph.node_weight(node_idx).unwrap().id);
+                    let dep_id = self.graph.node_weight(edge_idx.target()).unwrap().id;
+                    let combined = targets_to_here.intersect(&edge_idx.weight().targets);
+
+                    reached.entry(dep_id).or_insert_with(|| combined.clone());
+                    pending.insert(dep_id);
                 }
             } else {
                 eprintln!(
@@ -102,9 +139,50 @@ impl Graph {
             }
         }
 
-        processed.remove(&root_pkg_id);
+        reached.remove(&root_pkg_id);
 
-        processed
+        reached
+    }
+
+    /// The target triples under which `dep_id` is pulled in as a direct
+    /// dependency of `pkg_id`, or `None` if `dep_id` isn't a dependency of
+    /// `pkg_id` at all.
+    ///
+    /// This is distinct from an unconditional dependency, which is
+    /// `Some(EdgeTargets::All)` — callers that only checked for `None`
+    /// before couldn't tell the two cases apart.
+    pub fn get_active_targets_of(&self, pkg_id: PackageId, dep_id: PackageId) -> Option<EdgeTargets> {
+        let from = *self.nodes.get(&pkg_id)?;
+        let to = *self.nodes.get(&dep_id)?;
+        let edge = self.graph.find_edge(from, to)?;
+        Some(match &self.graph.edge_weight(edge)?.targets {
+            Some(targets) => EdgeTargets::Only(targets.clone()),
+            None => EdgeTargets::All,
+        })
+    }
+}
+
+/// Which targets, if any, gate a dependency edge (or a path of edges)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EdgeTargets {
+    /// Active unconditionally, on every target, or the graph was built
+    /// without any targets to check platform restrictions against
+    All,
+    /// Active only under these target triples
+    Only(BTreeSet<String>),
+}
+
+impl EdgeTargets {
+    /// Combine this (the restriction in effect so far along a path) with
+    /// one more edge's own restriction: the result is only as permissive as
+    /// the more restrictive of the two.
+    fn intersect(&self, edge_targets: &Option<BTreeSet<String>>) -> EdgeTargets {
+        match (self, edge_targets) {
+            (EdgeTargets::All, None) => EdgeTargets::All,
+            (EdgeTargets::All, Some(t)) => EdgeTargets::Only(t.clone()),
+            (EdgeTargets::Only(t), None) => EdgeTargets::Only(t.clone()),
+            (EdgeTargets::Only(a), Some(b)) => EdgeTargets::Only(a.intersection(b).cloned().collect()),
+        }
     }
 }
 
@@ -133,16 +211,12 @@ fn our_resolve<'a, 'cfg>(
     all_features: bool,
     no_default_features: bool,
     no_dev_dependencies: bool,
+    frozen: bool,
 ) -> CargoResult<(PackageSet<'cfg>, Resolve)> {
     // there is bunch of slightly different ways to do it,
     // so I leave some dead code around, in case I want to
     // try the other ones, in some near future
 
-    // this one will create a `Cargo.lock` file if it didn't exist before
-    // good? not good? it also uses the registry to make it possible
-    // the other methods
-    let (packages, resolve) = ops::resolve_ws(workspace)?;
-
     let method = Method::Required {
         dev_deps: !no_dev_dependencies,
         features: Rc::new(features.iter().map(|s| InternedString::new(s)).collect()),
@@ -156,6 +230,34 @@ fn our_resolve<'a, 'cfg>(
         .map(PackageIdSpec::from_package_id)
         .collect();
 
+    if frozen {
+        // `--frozen` means the lockfile on disk must already be accurate;
+        // don't let `ops::resolve_ws` below create or touch it, just
+        // resolve precisely against what's there, the same as cargo itself
+        // does for `--frozen`.
+        //
+        // `ops::resolve_ws_precisely` has no `no_dev_dependencies` parameter,
+        // unlike the `Method::Required { dev_deps, .. }` used on the
+        // non-frozen path below, so there's no way to honor the flag here.
+        // Reject the combination explicitly rather than silently resolving
+        // dev-dependencies anyway.
+        if no_dev_dependencies {
+            bail!("`--frozen` together with `--no-dev-dependencies` is not supported");
+        }
+        return ops::resolve_ws_precisely(
+            workspace,
+            features,
+            all_features,
+            no_default_features,
+            &specs,
+        );
+    }
+
+    // this one will create a `Cargo.lock` file if it didn't exist before
+    // good? not good? it also uses the registry to make it possible
+    // the other methods
+    let (packages, resolve) = ops::resolve_ws(workspace)?;
+
     let resolve = ops::resolve_with_previous(
         registry,
         workspace,
@@ -168,21 +270,6 @@ fn our_resolve<'a, 'cfg>(
 
     Ok((packages, resolve))
 
-    /*
-    // this method does not allow passing no_dev_dependencies
-    let specs: Vec<_> = roots
-        .map(|id| PackageIdSpec::from_package_id(id))
-        .collect();
-
-    ops::resolve_ws_precisely(
-        workspace,
-        features,
-        all_features,
-        no_default_features,
-        &specs,
-    )
-    */
-
     /*
     // this does not update/create `Cargo.lock` AFAIU
     let method = Method::Required {
@@ -200,12 +287,26 @@ fn our_resolve<'a, 'cfg>(
     */
 }
 
+/// A target triple plus the `cfg`s it was compiled with, as returned by
+/// `get_cfgs` for that triple
+struct TargetCfgs {
+    triple: String,
+    cfgs: Vec<Cfg>,
+}
+
+/// Build the dependency graph reachable from `roots`
+///
+/// When `targets` is empty, every edge is kept regardless of its
+/// platform/cfg restriction (the historical, host-agnostic behavior). When
+/// `targets` is non-empty, an edge is kept if its platform matches *any* of
+/// the given targets, and the matching subset is recorded on the edge so
+/// callers can tell a dependency that's always active from one that's only
+/// pulled in on, say, `wasm32-unknown-unknown`.
 fn build_graph<'a>(
     resolve: &'a Resolve,
     packages: &'a PackageSet<'_>,
     roots: impl Iterator<Item = PackageId>,
-    target: Option<&str>,
-    cfgs: Option<&[Cfg]>,
+    targets: &[TargetCfgs],
 ) -> CargoResult<Graph> {
     let mut graph = Graph {
         graph: petgraph::Graph::new(),
@@ -230,18 +331,32 @@ fn build_graph<'a>(
             let it = pkg
                 .dependencies()
                 .iter()
-                .filter(|d| d.matches_ignoring_source(raw_dep_id))
-                .filter(|d| {
-                    d.platform()
-                        .and_then(|p| target.map(|t| p.matches(t, cfgs)))
-                        .unwrap_or(true)
-                });
+                .filter(|d| d.matches_ignoring_source(raw_dep_id));
 
             let dep_id = match resolve.replacement(raw_dep_id) {
                 Some(id) => id,
                 None => raw_dep_id,
             };
             for dep in it {
+                let active_targets = match dep.platform() {
+                    None => None,
+                    Some(platform) => {
+                        if targets.is_empty() {
+                            None
+                        } else {
+                            let matching: BTreeSet<String> = targets
+                                .iter()
+                                .filter(|t| platform.matches(t.triple.as_str(), Some(&t.cfgs)))
+                                .map(|t| t.triple.clone())
+                                .collect();
+                            if matching.is_empty() {
+                                continue;
+                            }
+                            Some(matching)
+                        }
+                    }
+                };
+
                 let dep_idx = match graph.nodes.entry(dep_id) {
                     Entry::Occupied(e) => *e.get(),
                     Entry::Vacant(e) => {
@@ -253,7 +368,14 @@ fn build_graph<'a>(
                         *e.insert(graph.graph.add_node(node))
                     }
                 };
-                graph.graph.add_edge(idx, dep_idx, dep.kind());
+                graph.graph.add_edge(
+                    idx,
+                    dep_idx,
+                    DepEdge {
+                        kind: dep.kind(),
+                        targets: active_targets,
+                    },
+                );
             }
         }
     }
@@ -289,9 +411,9 @@ impl Repo {
             0,
             None,
             &None,
-            /* frozen: */ false,
+            /* frozen: */ cargo_opts.frozen,
             /* locked: */ true,
-            /* offline: */ false,
+            /* offline: */ cargo_opts.offline,
             &None,
             &cargo_opts.unstable_flags,
         )?;
@@ -351,28 +473,76 @@ impl Repo {
             self.cargo_opts.all_features,
             self.cargo_opts.no_default_features,
             self.cargo_opts.no_dev_dependencies,
+            self.cargo_opts.frozen,
         )?;
 
         let rustc = self.config.load_global_rustc(Some(&workspace))?;
 
         let target = if let Some(ref target) = self.cargo_opts.target {
-            Some(target.as_ref().unwrap_or(&rustc.host).as_str())
+            Some(target.as_ref().unwrap_or(&rustc.host).clone())
         } else {
             None
         };
 
-        let cfgs = get_cfgs(&rustc, target)?;
-        let graph = build_graph(
-            &resolve,
-            &packages,
-            roots.into_iter(),
-            target,
-            cfgs.as_ref().map(|r| &**r),
-        )?;
+        let targets = match target {
+            Some(triple) => {
+                let cfgs = get_cfgs(&rustc, Some(triple.as_str()))?.unwrap_or_default();
+                vec![TargetCfgs { triple, cfgs }]
+            }
+            None => vec![],
+        };
+
+        let graph = build_graph(&resolve, &packages, roots.into_iter(), &targets)?;
 
         Ok(graph)
     }
 
+    /// Like [`Repo::get_dependency_graph`], but takes the union of dependency
+    /// edges across every target in `target_triples` instead of a single
+    /// host-derived one.
+    ///
+    /// This is how `verify` can be made to see Windows-only or wasm-only
+    /// dependencies that would otherwise silently disappear from a
+    /// single-target graph.
+    pub fn get_dependency_graph_for_targets(
+        &self,
+        roots: Vec<PackageId>,
+        target_triples: &[String],
+    ) -> CargoResult<Graph> {
+        let workspace = self.workspace()?;
+
+        let mut registry = self.registry(
+            workspace
+                .members()
+                .map(|m| m.summary().source_id().to_owned()),
+        )?;
+
+        let (packages, resolve) = our_resolve(
+            &mut registry,
+            &workspace,
+            &self.features_list,
+            self.cargo_opts.all_features,
+            self.cargo_opts.no_default_features,
+            self.cargo_opts.no_dev_dependencies,
+            self.cargo_opts.frozen,
+        )?;
+
+        let rustc = self.config.load_global_rustc(Some(&workspace))?;
+
+        let targets = target_triples
+            .iter()
+            .map(|triple| {
+                let cfgs = get_cfgs(&rustc, Some(triple.as_str()))?.unwrap_or_default();
+                Ok(TargetCfgs {
+                    triple: triple.clone(),
+                    cfgs,
+                })
+            })
+            .collect::<CargoResult<Vec<_>>>()?;
+
+        build_graph(&resolve, &packages, roots.into_iter(), &targets)
+    }
+
     pub fn update_source(&self) -> Result<()> {
         let mut source = self.load_source()?;
         let _lock = self.config.acquire_package_cache_lock()?;
@@ -435,6 +605,7 @@ impl Repo {
             self.cargo_opts.all_features,
             self.cargo_opts.no_default_features,
             self.cargo_opts.no_dev_dependencies,
+            self.cargo_opts.frozen,
         )?;
         let mut source = self.load_source()?;
 
@@ -476,6 +647,7 @@ impl Repo {
             self.cargo_opts.all_features,
             self.cargo_opts.no_default_features,
             self.cargo_opts.no_dev_dependencies,
+            self.cargo_opts.frozen,
         )?;
 
         for pkg_id in package_set.package_ids() {
@@ -516,6 +688,7 @@ impl Repo {
             self.cargo_opts.all_features,
             self.cargo_opts.no_default_features,
             self.cargo_opts.no_dev_dependencies,
+            self.cargo_opts.frozen,
         )?)
     }
 
@@ -633,4 +806,381 @@ impl Repo {
                 .collect())
         }
     }
+
+    /// Build a consolidated "should I trust/upgrade this crate" report
+    ///
+    /// Combines the registry metadata and download counts we already fetch
+    /// elsewhere with the crate's crev review coverage and its number of
+    /// direct reverse-dependents in the current workspace graph.
+    pub fn get_crate_info(&self, sel: &CrateSelector) -> Result<CrateInfoReport> {
+        let pkg_id = self.find_pkgid_by_crate_selector(sel)?;
+        let pkg = self.get_crate(&pkg_id)?;
+        let metadata = pkg.manifest().metadata().clone();
+
+        let local = crev_lib::Local::auto_create_or_open()?;
+        let crates_io = crates_io::Client::new(&local)?;
+        let downloads = crates_io
+            .get_downloads_count(&pkg_id.name(), &pkg_id.version())
+            .ok();
+
+        let mut source = self.load_source()?;
+        let _lock = self.config.acquire_package_cache_lock()?;
+        let dependency_request =
+            Dependency::parse_no_deprecated(pkg_id.name().as_str(), None, pkg_id.source_id())?;
+        let mut summaries = vec![];
+        source.query(&dependency_request, &mut |s| summaries.push(s.clone()))?;
+        let mut versions: Vec<_> = summaries.into_iter().map(|s| s.version().clone()).collect();
+        versions.sort();
+
+        let reviewed_versions = versions
+            .iter()
+            .map(|v| {
+                let status = local
+                    .get_package_verification_status(pkg_id.name().as_str(), &v.to_string())
+                    .unwrap_or(crev_lib::VerificationStatus::None);
+                (v.clone(), status)
+            })
+            .collect();
+
+        let workspace = self.workspace()?;
+        let roots: Vec<_> = workspace.members().map(|m| m.package_id()).collect();
+        let reverse_dependents = self
+            .get_dependency_graph(roots)?
+            .get_reverse_dependencies_of(pkg_id)
+            .count();
+
+        Ok(CrateInfoReport {
+            id: pkg_id,
+            metadata,
+            downloads,
+            versions,
+            reviewed_versions,
+            reverse_dependents,
+        })
+    }
+}
+
+/// Consolidated registry + review-coverage report for a single crate
+///
+/// Printed by `crev info <crate>`: a one-shot view of what a crate is,
+/// whether anyone has reviewed the versions on offer, and how many of our
+/// own dependencies would be affected by touching it.
+///
+/// Named `CrateInfoReport` (rather than `CrateInfo`) to avoid colliding with
+/// [`crate::deps::CrateInfo`], the per-row data backing the `verify` table.
+#[derive(Debug)]
+pub struct CrateInfoReport {
+    pub id: PackageId,
+    pub metadata: ManifestMetadata,
+    pub downloads: Option<crates_io::Downloads>,
+    pub versions: Vec<Version>,
+    pub reviewed_versions: Vec<(Version, crev_lib::VerificationStatus)>,
+    pub reverse_dependents: usize,
+}
+
+/// Options controlling a trust-aware `update`
+///
+/// Modeled the same way as the other `*Options` structs that get threaded
+/// through `Repo`: a plain data bag built by the CLI layer and handed to a
+/// single entry point.
+#[derive(Debug, Clone, Default)]
+pub struct UpdateOptions {
+    /// Crates to update; empty means "every dependency"
+    pub to_update: Vec<String>,
+    /// Pin a single crate to an exact version instead of picking the best-trusted one
+    pub precise: Option<String>,
+    /// Also re-pin transitive dependencies of the named crates
+    pub recursive: bool,
+    /// Print the planned changes without touching `Cargo.lock`
+    pub dry_run: bool,
 }
+
+/// A single crate whose version we're about to change, and why
+#[derive(Debug)]
+struct PlannedUpdate {
+    name: String,
+    from: Version,
+    to: Version,
+    trust_score: i64,
+}
+
+/// Score a candidate `name@version` by its aggregated crev trust/review level
+///
+/// Higher is better. This is intentionally coarse: we only need a total
+/// order over candidates, not a calibrated probability.
+fn trust_score_for(local: &crev_lib::Local, name: &str, version: &Version) -> CargoResult<i64> {
+    use crev_lib::VerificationStatus::*;
+
+    let status = local
+        .get_package_verification_status(name, &version.to_string())
+        .unwrap_or(crev_lib::VerificationStatus::None);
+
+    Ok(match status {
+        Verified => 1_000,
+        Local => 900,
+        Negative => -1_000,
+        None => 0,
+    })
+}
+
+impl Repo {
+    /// Rewrite `Cargo.lock` toward the most-trusted version of each
+    /// dependency that still satisfies the workspace's semver requirements.
+    ///
+    /// Unlike a plain `cargo update`, which always jumps to the newest
+    /// compatible version, this walks every candidate in range and picks the
+    /// one with the best aggregated crev trust, falling back to highest
+    /// semver to break ties.
+    pub fn update(&self, opts: &UpdateOptions) -> Result<()> {
+        let workspace = self.workspace()?;
+        let mut registry = self.registry(
+            workspace
+                .members()
+                .map(|m| m.summary().source_id().to_owned()),
+        )?;
+
+        let (packages, resolve) = our_resolve(
+            &mut registry,
+            &workspace,
+            &self.features_list,
+            self.cargo_opts.all_features,
+            self.cargo_opts.no_default_features,
+            self.cargo_opts.no_dev_dependencies,
+            self.cargo_opts.frozen,
+        )?;
+
+        let roots: Vec<_> = workspace.members().map(|m| m.package_id()).collect();
+        let graph = build_graph(&resolve, &packages, roots.into_iter(), &[])?;
+
+        let wanted: HashSet<&str> = opts.to_update.iter().map(String::as_str).collect();
+        let local = crev_lib::Local::auto_create_or_open()?;
+
+        // `--recursive` only widens the scope to the *transitive dependencies
+        // of the named crates*, not to every crate in the graph.
+        let recursive_closure: HashSet<PackageId> = if opts.recursive {
+            graph
+                .get_all_pkg_ids()
+                .filter(|id| wanted.contains(id.name().as_str()))
+                .flat_map(|id| graph.get_recursive_dependencies_of(id).into_iter().map(|(k, _)| k))
+                .collect()
+        } else {
+            HashSet::new()
+        };
+
+        let mut source = self.load_source()?;
+        let _lock = self.config.acquire_package_cache_lock()?;
+
+        let mut precise = HashMap::new();
+        let mut planned = vec![];
+
+        for pkg_id in graph.get_all_pkg_ids() {
+            if !pkg_id.source_id().is_registry() {
+                continue;
+            }
+            let name = pkg_id.name().as_str();
+            if !wanted.is_empty()
+                && !wanted.contains(name)
+                && !recursive_closure.contains(&pkg_id)
+            {
+                continue;
+            }
+
+            // A crate doesn't depend on itself, so the semver requirement we
+            // need to stay within comes from whoever *depends on* `pkg_id`,
+            // not from `pkg_id`'s own dependency list. Different dependents
+            // can each narrow the range, so a candidate must satisfy *every*
+            // dependent's requirement, not just the first one found.
+            let version_reqs: Vec<VersionReq> = graph
+                .get_reverse_dependencies_of(pkg_id)
+                .filter_map(|dependent_id| {
+                    let dependent = packages.get_one(dependent_id).ok()?;
+                    dependent
+                        .dependencies()
+                        .iter()
+                        .find(|d| d.package_name().as_str() == name)
+                        .map(|d| d.version_req().clone())
+                })
+                .collect();
+            if version_reqs.is_empty() {
+                continue;
+            }
+
+            if let Some(ref precise_version) = opts.precise {
+                if wanted.contains(name) {
+                    let to = Version::parse(precise_version)?;
+                    planned.push(PlannedUpdate {
+                        name: name.to_owned(),
+                        from: pkg_id.version().clone(),
+                        trust_score: trust_score_for(&local, name, &to).unwrap_or(0),
+                        to,
+                    });
+                    precise.insert(name.to_owned(), precise_version.clone());
+                    continue;
+                }
+            }
+
+            let dependency_request =
+                Dependency::parse_no_deprecated(name, None, pkg_id.source_id())?;
+            let mut summaries = vec![];
+            source.query(&dependency_request, &mut |s| summaries.push(s.clone()))?;
+
+            let mut candidates: Vec<_> = summaries
+                .into_iter()
+                .filter(|s| version_reqs.iter().all(|req| req.matches(s.version())))
+                .collect();
+            candidates.sort_by_key(|s| s.version().clone());
+
+            let best = candidates.iter().max_by_key(|s| {
+                let score = trust_score_for(&local, name, s.version()).unwrap_or(0);
+                (score, s.version().clone())
+            });
+
+            if let Some(best) = best {
+                if best.version() != &pkg_id.version() {
+                    planned.push(PlannedUpdate {
+                        name: name.to_owned(),
+                        from: pkg_id.version().clone(),
+                        to: best.version().clone(),
+                        trust_score: trust_score_for(&local, name, best.version()).unwrap_or(0),
+                    });
+                    precise.insert(name.to_owned(), best.version().to_string());
+                }
+            }
+        }
+
+        if opts.dry_run {
+            for update in &planned {
+                println!(
+                    "{} {} -> {} (trust score {})",
+                    update.name, update.from, update.to, update.trust_score
+                );
+            }
+            return Ok(());
+        }
+
+        if precise.is_empty() {
+            return Ok(());
+        }
+
+        let method = Method::Required {
+            dev_deps: !self.cargo_opts.no_dev_dependencies,
+            features: Rc::new(
+                self.features_list
+                    .iter()
+                    .map(|s| InternedString::new(s))
+                    .collect(),
+            ),
+            all_features: self.cargo_opts.all_features,
+            uses_default_features: !self.cargo_opts.no_default_features,
+        };
+        let specs: Vec<_> = workspace
+            .members()
+            .map(|m| m.summary().package_id())
+            .map(PackageIdSpec::from_package_id)
+            .collect();
+
+        let mut new_resolve = ops::resolve_with_previous(
+            &mut registry,
+            &workspace,
+            method,
+            Some(&resolve),
+            Some(&precise),
+            &specs,
+            true,
+        )?;
+
+        ops::write_pkg_lockfile(&workspace, &mut new_resolve)?;
+
+        Ok(())
+    }
+}
+
+/// Which manifest table a new dependency should be inserted into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AddKind {
+    Normal,
+    Dev,
+    Build,
+}
+
+impl AddKind {
+    fn table_name(self) -> &'static str {
+        match self {
+            AddKind::Normal => "dependencies",
+            AddKind::Dev => "dev-dependencies",
+            AddKind::Build => "build-dependencies",
+        }
+    }
+}
+
+/// Options controlling a verification-gated `add`
+#[derive(Debug, Clone)]
+pub struct AddOptions {
+    /// `name` or `name@version`
+    pub spec: String,
+    pub kind: AddKind,
+    /// Insert the dependency even if no acceptable crev review exists for it
+    pub allow_unreviewed: bool,
+    /// Print the planned manifest edit without writing it
+    pub dry_run: bool,
+}
+
+impl Repo {
+    /// Insert a dependency into `Cargo.toml`, refusing to do so when the
+    /// resolved version has no acceptable crev review.
+    ///
+    /// Uses `toml_edit` rather than the `toml` crate so any formatting or
+    /// comments already in the manifest survive the edit.
+    pub fn add(&self, opts: &AddOptions) -> Result<()> {
+        let (name, version) = match opts.spec.find('@') {
+            Some(idx) => (
+                &opts.spec[..idx],
+                Some(Version::parse(&opts.spec[idx + 1..])?),
+            ),
+            None => (opts.spec.as_str(), None),
+        };
+
+        let pkg_id = self
+            .find_independent_pkg_id_by_selector(name, version.as_ref())?
+            .ok_or_else(|| format_err!("Could not find requested crate"))?;
+
+        if !opts.allow_unreviewed {
+            let local = crev_lib::Local::auto_create_or_open()?;
+            let status = local
+                .get_package_verification_status(name, &pkg_id.version().to_string())
+                .unwrap_or(crev_lib::VerificationStatus::None);
+            // Only an actual positive review counts as "acceptable" here:
+            // `None` means nobody looked, and `Negative` means somebody did
+            // and flagged it, which is an even stronger reason to refuse.
+            let acceptable = matches!(
+                status,
+                crev_lib::VerificationStatus::Verified | crev_lib::VerificationStatus::Local
+            );
+            if !acceptable {
+                bail!(
+                    "{} {} has no acceptable crev review ({:?}); pass --allow-unreviewed to add it anyway",
+                    name,
+                    pkg_id.version(),
+                    status
+                );
+            }
+        }
+
+        let manifest_contents = std::fs::read_to_string(&self.manifest_path)?;
+        let mut document = manifest_contents.parse::<toml_edit::Document>()?;
+
+        let table = document[opts.kind.table_name()]
+            .or_insert(toml_edit::Item::Table(toml_edit::Table::new()));
+        table[name] = toml_edit::value(pkg_id.version().to_string());
+
+        if opts.dry_run {
+            println!("{}", document.to_string());
+            return Ok(());
+        }
+
+        std::fs::write(&self.manifest_path, document.to_string())?;
+
+        Ok(())
+    }
+}
+
@@ -0,0 +1,234 @@
+// Thin wrapper around the `term` crate for colored terminal output, kept
+// separate from `deps::print_term` so printing logic doesn't need to know
+// whether colors are actually supported on the current output.
+
+use crate::deps::MaintenanceStatus;
+use crev_lib::VerificationStatus;
+use std::collections::HashMap;
+use term::color::Color;
+
+pub struct Term {
+    stdout: Option<Box<term::StdoutTerminal>>,
+}
+
+impl Term {
+    pub fn new() -> Self {
+        Term {
+            stdout: term::stdout(),
+        }
+    }
+
+    pub fn print(
+        &mut self,
+        args: std::fmt::Arguments<'_>,
+        color: Option<Color>,
+    ) -> crate::prelude::Result<()> {
+        match (&mut self.stdout, color) {
+            (Some(t), Some(color)) => {
+                t.fg(color)?;
+                print!("{}", args);
+                t.reset()?;
+            }
+            _ => print!("{}", args),
+        }
+        Ok(())
+    }
+}
+
+/// Every semantic meaning a color can carry in `verify` output
+///
+/// `Theme` maps each of these to a concrete `term::color::Color`, so every
+/// callsite that used to reach for a literal `color::YELLOW` asks the
+/// theme for a role instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Role {
+    TrustVerified,
+    TrustLocal,
+    TrustNone,
+    TrustNegative,
+    LowDownloads,
+    LowOwners,
+    IssuesPresent,
+    IssuesKnown,
+    Unmaintained,
+    ScoreLow,
+    ScoreMedium,
+    LargeTransitiveSize,
+    MaintenanceGood,
+    MaintenanceCaution,
+    MaintenanceDeprecated,
+    StaleRelease,
+    VeryStaleRelease,
+}
+
+/// A named mapping from semantic `Role` to terminal color
+///
+/// `Theme::default()` matches the colors this module used before themes
+/// existed. `Theme::monochrome()` disables color entirely (for piping to a
+/// file) and `Theme::high_contrast()` swaps in a colorblind-friendlier
+/// palette; all three, plus any theme loaded from crev config, go through
+/// the same `color_for` so callers never special-case "no color".
+#[derive(Debug, Clone)]
+pub struct Theme {
+    colors: HashMap<Role, Color>,
+}
+
+impl Theme {
+    fn from_pairs(pairs: &[(Role, Color)]) -> Self {
+        Theme {
+            colors: pairs.iter().cloned().collect(),
+        }
+    }
+
+    pub fn default_theme() -> Self {
+        use Role::*;
+        Theme::from_pairs(&[
+            (TrustVerified, ::term::color::GREEN),
+            (TrustNone, ::term::color::YELLOW),
+            (TrustNegative, ::term::color::RED),
+            (LowDownloads, ::term::color::YELLOW),
+            (LowOwners, ::term::color::RED),
+            (IssuesPresent, ::term::color::RED),
+            (IssuesKnown, ::term::color::YELLOW),
+            (Unmaintained, ::term::color::YELLOW),
+            (ScoreLow, ::term::color::RED),
+            (ScoreMedium, ::term::color::YELLOW),
+            (LargeTransitiveSize, ::term::color::YELLOW),
+            (MaintenanceGood, ::term::color::GREEN),
+            (MaintenanceCaution, ::term::color::YELLOW),
+            (MaintenanceDeprecated, ::term::color::RED),
+            (StaleRelease, ::term::color::YELLOW),
+            (VeryStaleRelease, ::term::color::RED),
+        ])
+    }
+
+    pub fn monochrome() -> Self {
+        Theme {
+            colors: HashMap::new(),
+        }
+    }
+
+    pub fn high_contrast() -> Self {
+        use Role::*;
+        Theme::from_pairs(&[
+            (TrustVerified, ::term::color::BRIGHT_GREEN),
+            (TrustNone, ::term::color::BRIGHT_BLUE),
+            (TrustNegative, ::term::color::BRIGHT_RED),
+            (LowDownloads, ::term::color::BRIGHT_BLUE),
+            (LowOwners, ::term::color::BRIGHT_RED),
+            (IssuesPresent, ::term::color::BRIGHT_RED),
+            (IssuesKnown, ::term::color::BRIGHT_BLUE),
+            (Unmaintained, ::term::color::BRIGHT_BLUE),
+            (ScoreLow, ::term::color::BRIGHT_RED),
+            (ScoreMedium, ::term::color::BRIGHT_BLUE),
+            (LargeTransitiveSize, ::term::color::BRIGHT_BLUE),
+            (MaintenanceGood, ::term::color::BRIGHT_GREEN),
+            (MaintenanceCaution, ::term::color::BRIGHT_BLUE),
+            (MaintenanceDeprecated, ::term::color::BRIGHT_RED),
+            (StaleRelease, ::term::color::BRIGHT_BLUE),
+            (VeryStaleRelease, ::term::color::BRIGHT_RED),
+        ])
+    }
+
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Theme::default_theme()),
+            "monochrome" => Some(Theme::monochrome()),
+            "high-contrast" => Some(Theme::high_contrast()),
+            _ => None,
+        }
+    }
+
+    /// Load the theme named in crev's config, falling back to the built-in
+    /// default when unset or unrecognized
+    pub fn load_from_config(local: &crev_lib::Local) -> Self {
+        local
+            .get_config_theme_name()
+            .ok()
+            .flatten()
+            .and_then(|name| Theme::by_name(&name))
+            .unwrap_or_else(Theme::default_theme)
+    }
+
+    /// Apply `--theme`/`--no-color` CLI overrides on top of whatever was
+    /// loaded from config
+    pub fn resolve(config_theme: Theme, theme_override: Option<&str>, no_color: bool) -> Self {
+        if no_color {
+            return Theme::monochrome();
+        }
+        match theme_override.and_then(Theme::by_name) {
+            Some(theme) => theme,
+            None => config_theme,
+        }
+    }
+
+    pub fn color_for(&self, role: Role) -> Option<Color> {
+        self.colors.get(&role).cloned()
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::default_theme()
+    }
+}
+
+pub fn verification_status_color(theme: &Theme, status: VerificationStatus) -> Option<Color> {
+    use VerificationStatus::*;
+
+    match status {
+        Verified => theme.color_for(Role::TrustVerified),
+        Local => theme.color_for(Role::TrustLocal),
+        Negative => theme.color_for(Role::TrustNegative),
+        None => theme.color_for(Role::TrustNone),
+    }
+}
+
+/// Color for the upstream-declared `maintenance.status` flag, distinct from
+/// `verification_status_color`'s crev-reviewer-driven trust coloring
+pub fn maintenance_status_color(theme: &Theme, status: MaintenanceStatus) -> Option<Color> {
+    use MaintenanceStatus::*;
+
+    match status {
+        ActivelyDeveloped | PassivelyMaintained => theme.color_for(Role::MaintenanceGood),
+        AsIs | Experimental | LookingForMaintainer => theme.color_for(Role::MaintenanceCaution),
+        Deprecated => theme.color_for(Role::MaintenanceDeprecated),
+    }
+}
+
+/// Staleness thresholds for [`release_freshness_color`], on the same
+/// `[0, 1]` scale as [`crate::deps::freshness_score`]
+const RELEASE_STALE_THRESHOLD: f64 = 0.4;
+const RELEASE_VERY_STALE_THRESHOLD: f64 = 0.15;
+
+/// Color a release-freshness score, using the same red/yellow/none shape as
+/// `score_color`
+pub fn release_freshness_color(theme: &Theme, score: f64) -> Option<Color> {
+    if score < RELEASE_VERY_STALE_THRESHOLD {
+        theme.color_for(Role::VeryStaleRelease)
+    } else if score < RELEASE_STALE_THRESHOLD {
+        theme.color_for(Role::StaleRelease)
+    } else {
+        None
+    }
+}
+
+pub fn known_owners_count_color(theme: &Theme, count: u64) -> Option<Color> {
+    if count == 0 {
+        theme.color_for(Role::LowOwners)
+    } else {
+        None
+    }
+}
+
+/// Color the composite quality score cell: red below 40, yellow below 70,
+/// uncolored otherwise
+pub fn score_color(theme: &Theme, score: f64) -> Option<Color> {
+    if score < 40.0 {
+        theme.color_for(Role::ScoreLow)
+    } else if score < 70.0 {
+        theme.color_for(Role::ScoreMedium)
+    } else {
+        None
+    }
+}